@@ -0,0 +1,68 @@
+//! Groups a stream of [`SdRecord`]s into bounded [`RecordBatch`]es.
+
+use arrow_array::RecordBatch;
+use iceberg::Result;
+
+use crate::record::{build_recordbatch, SdRecord};
+
+/// Number of records materialized into each `RecordBatch` when not overridden.
+pub const DEFAULT_BATCH_SIZE: usize = 50_000;
+
+/// Wraps any `SdRecord` source and yields one `RecordBatch` every `batch_size` records,
+/// so peak memory stays bounded to a single batch no matter how large the source is.
+pub struct BatchedRecordSource<S> {
+    source: S,
+    batch_size: usize,
+    done: bool,
+}
+
+impl<S> BatchedRecordSource<S>
+where
+    S: Iterator<Item = Result<SdRecord>>,
+{
+    pub fn new(source: S) -> Self {
+        Self::with_batch_size(source, DEFAULT_BATCH_SIZE)
+    }
+
+    pub fn with_batch_size(source: S, batch_size: usize) -> Self {
+        Self {
+            source,
+            batch_size,
+            done: false,
+        }
+    }
+}
+
+impl<S> Iterator for BatchedRecordSource<S>
+where
+    S: Iterator<Item = Result<SdRecord>>,
+{
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut records = Vec::with_capacity(self.batch_size);
+        while records.len() < self.batch_size {
+            match self.source.next() {
+                Some(Ok(record)) => records.push(record),
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+
+        if records.is_empty() {
+            None
+        } else {
+            Some(build_recordbatch(&records))
+        }
+    }
+}
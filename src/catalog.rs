@@ -0,0 +1,151 @@
+//! Selects and builds the Iceberg [`Catalog`] implementation the sink writes through.
+//!
+//! Tests and local runs default to the in-memory catalog; production deployments
+//! point at a real metastore (REST, JDBC, or Glue) by setting connection config,
+//! without any code changes to the write path.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use iceberg::memory::{MemoryCatalogBuilder, MEMORY_CATALOG_WAREHOUSE};
+use iceberg::{Catalog, CatalogBuilder, Error, ErrorKind, Result};
+use iceberg_catalog_glue::{GlueCatalogBuilder, GLUE_CATALOG_PROP_URI, GLUE_CATALOG_PROP_WAREHOUSE};
+use iceberg_catalog_rest::{RestCatalogBuilder, REST_CATALOG_PROP_URI, REST_CATALOG_PROP_WAREHOUSE};
+use iceberg_catalog_sql::{SqlCatalogBuilder, SQL_CATALOG_PROP_URI, SQL_CATALOG_PROP_WAREHOUSE};
+
+/// Which catalog implementation to build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogKind {
+    /// Non-durable, in-process catalog. The default; used for tests and local runs.
+    Memory,
+    /// A REST catalog (e.g. Tabular, Polaris, or any `iceberg-rest` server).
+    Rest,
+    /// A JDBC-backed SQL catalog (Postgres, MySQL, SQLite, ...).
+    Jdbc,
+    /// AWS Glue Data Catalog.
+    Glue,
+}
+
+/// Connection details for building a catalog. `uri` and `credentials` are ignored by
+/// [`CatalogKind::Memory`].
+#[derive(Debug, Clone)]
+pub struct CatalogConfig {
+    pub kind: CatalogKind,
+    pub warehouse: String,
+    pub uri: Option<String>,
+    pub credentials: HashMap<String, String>,
+}
+
+impl CatalogConfig {
+    /// The default configuration: an in-memory catalog rooted at `warehouse`.
+    /// Not yet called from `main`, which always goes through [`CatalogConfig::from_env`];
+    /// kept for tests and other embedders that want an in-memory catalog without env vars.
+    #[allow(dead_code)]
+    pub fn memory(warehouse: impl Into<String>) -> Self {
+        Self {
+            kind: CatalogKind::Memory,
+            warehouse: warehouse.into(),
+            uri: None,
+            credentials: HashMap::new(),
+        }
+    }
+
+    /// Reads the catalog configuration from the environment, defaulting to
+    /// [`CatalogKind::Memory`] so local runs and tests need no setup:
+    ///
+    /// - `ICEBERG_CATALOG_KIND`: `memory` (default), `rest`, `jdbc`, or `glue`
+    /// - `ICEBERG_CATALOG_WAREHOUSE`: warehouse location (default `file:///tmp/iceberg_warehouse`)
+    /// - `ICEBERG_CATALOG_URI`: connection URI (required for `rest`/`jdbc`)
+    /// - `ICEBERG_CATALOG_CRED_<NAME>`: forwarded to the backend as credential `<name>` (lowercased)
+    pub fn from_env() -> Result<Self> {
+        let kind = match std::env::var("ICEBERG_CATALOG_KIND") {
+            Ok(kind) => match kind.to_ascii_lowercase().as_str() {
+                "memory" => CatalogKind::Memory,
+                "rest" => CatalogKind::Rest,
+                "jdbc" => CatalogKind::Jdbc,
+                "glue" => CatalogKind::Glue,
+                other => {
+                    return Err(Error::new(
+                        ErrorKind::DataInvalid,
+                        format!("unknown ICEBERG_CATALOG_KIND `{other}`"),
+                    ))
+                }
+            },
+            Err(_) => CatalogKind::Memory,
+        };
+
+        let warehouse = std::env::var("ICEBERG_CATALOG_WAREHOUSE")
+            .unwrap_or_else(|_| "file:///tmp/iceberg_warehouse".to_string());
+        let uri = std::env::var("ICEBERG_CATALOG_URI").ok();
+
+        let credentials = std::env::vars()
+            .filter_map(|(key, value)| {
+                key.strip_prefix("ICEBERG_CATALOG_CRED_")
+                    .map(|name| (name.to_ascii_lowercase(), value))
+            })
+            .collect();
+
+        Ok(Self {
+            kind,
+            warehouse,
+            uri,
+            credentials,
+        })
+    }
+}
+
+/// Builds the `Catalog` described by `config`, behind a shared `Arc` so the sink can
+/// be handed a single catalog instance regardless of which backend it is.
+pub async fn build_catalog(config: CatalogConfig) -> Result<Arc<dyn Catalog>> {
+    match config.kind {
+        CatalogKind::Memory => {
+            let catalog = MemoryCatalogBuilder::default()
+                .load(
+                    "memory",
+                    HashMap::from([(MEMORY_CATALOG_WAREHOUSE.to_string(), config.warehouse)]),
+                )
+                .await?;
+            Ok(Arc::new(catalog))
+        }
+
+        CatalogKind::Rest => {
+            let uri = require_uri(&config)?;
+            let mut props = config.credentials.clone();
+            props.insert(REST_CATALOG_PROP_URI.to_string(), uri);
+            props.insert(REST_CATALOG_PROP_WAREHOUSE.to_string(), config.warehouse);
+
+            let catalog = RestCatalogBuilder::default().load("rest", props).await?;
+            Ok(Arc::new(catalog))
+        }
+
+        CatalogKind::Jdbc => {
+            let uri = require_uri(&config)?;
+            let mut props = config.credentials.clone();
+            props.insert(SQL_CATALOG_PROP_URI.to_string(), uri);
+            props.insert(SQL_CATALOG_PROP_WAREHOUSE.to_string(), config.warehouse);
+
+            let catalog = SqlCatalogBuilder::default().load("jdbc", props).await?;
+            Ok(Arc::new(catalog))
+        }
+
+        CatalogKind::Glue => {
+            let mut props = config.credentials.clone();
+            if let Some(uri) = config.uri.clone() {
+                props.insert(GLUE_CATALOG_PROP_URI.to_string(), uri);
+            }
+            props.insert(GLUE_CATALOG_PROP_WAREHOUSE.to_string(), config.warehouse);
+
+            let catalog = GlueCatalogBuilder::default().load("glue", props).await?;
+            Ok(Arc::new(catalog))
+        }
+    }
+}
+
+fn require_uri(config: &CatalogConfig) -> Result<String> {
+    config.uri.clone().ok_or_else(|| {
+        Error::new(
+            ErrorKind::DataInvalid,
+            format!("catalog kind {:?} requires a connection URI", config.kind),
+        )
+    })
+}
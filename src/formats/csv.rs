@@ -0,0 +1,134 @@
+//! Reads CSV exports of mioty uplinks, one row per record with a header line.
+
+use std::path::Path;
+
+use hex::FromHex;
+
+use iceberg::{Error, ErrorKind, Result};
+
+use crate::record::SdRecord;
+
+/// Streams `SdRecord`s out of a CSV file whose header names match the `SdRecord`
+/// fields (`device_id`, `ts`, `rssi`, `snr`, `phy`, `frame_type`, `payload`, and the
+/// optional `mioty_qi_1`/`mioty_qi_2`/`mioty_qi_3` columns).
+pub struct CsvRecordSource {
+    reader: csv::Reader<std::fs::File>,
+}
+
+impl CsvRecordSource {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+
+        Ok(Self {
+            reader: csv::Reader::from_reader(file),
+        })
+    }
+}
+
+fn parse_record(row: &csv::StringRecord, headers: &csv::StringRecord) -> Result<SdRecord> {
+    let field = |name: &str| -> Result<&str> {
+        headers
+            .iter()
+            .position(|h| h == name)
+            .and_then(|i| row.get(i))
+            .ok_or_else(|| Error::new(ErrorKind::DataInvalid, format!("missing column `{name}`")))
+    };
+    let parse_int = |s: &str| -> Result<i64> {
+        s.parse::<i64>()
+            .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))
+    };
+
+    let mut record = SdRecord::new();
+    record.device_id = field("device_id")?.to_string();
+    record.ts = parse_int(field("ts")?)?;
+    record.rssi = parse_int(field("rssi")?)? as i32;
+    record.snr = parse_int(field("snr")?)? as i32;
+    record.phy = field("phy")?.to_string();
+    record.frame_type = field("frame_type")?.to_string();
+    record.payload = Vec::from_hex(field("payload")?)
+        .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+    record.mioty_qi_1 = field("mioty_qi_1").ok().and_then(|s| s.parse::<f64>().ok());
+    record.mioty_qi_2 = field("mioty_qi_2").ok().and_then(|s| s.parse::<i32>().ok());
+    record.mioty_qi_3 = field("mioty_qi_3").ok().and_then(|s| s.parse::<i32>().ok());
+
+    Ok(record)
+}
+
+impl Iterator for CsvRecordSource {
+    type Item = Result<SdRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let headers = match self.reader.headers() {
+            Ok(headers) => headers.clone(),
+            Err(e) => return Some(Err(Error::new(ErrorKind::DataInvalid, e.to_string()))),
+        };
+
+        let mut row = csv::StringRecord::new();
+        match self.reader.read_record(&mut row) {
+            Ok(true) => Some(parse_record(&row, &headers)),
+            Ok(false) => None,
+            Err(e) => Some(Err(Error::new(ErrorKind::DataInvalid, e.to_string()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> csv::StringRecord {
+        csv::StringRecord::from(vec![
+            "device_id",
+            "ts",
+            "rssi",
+            "snr",
+            "phy",
+            "frame_type",
+            "payload",
+            "mioty_qi_1",
+            "mioty_qi_2",
+            "mioty_qi_3",
+        ])
+    }
+
+    #[test]
+    fn parses_row_with_optional_columns() {
+        let row = csv::StringRecord::from(vec![
+            "dev-a", "1000", "-80", "5", "mioty", "uplink", "deadbeef", "0.5", "1", "2",
+        ]);
+
+        let record = parse_record(&row, &headers()).unwrap();
+        assert_eq!(record.device_id, "dev-a");
+        assert_eq!(record.ts, 1000);
+        assert_eq!(record.payload, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(record.mioty_qi_1, Some(0.5));
+        assert_eq!(record.mioty_qi_2, Some(1));
+        assert_eq!(record.mioty_qi_3, Some(2));
+    }
+
+    #[test]
+    fn errors_on_missing_required_column() {
+        let headers = csv::StringRecord::from(vec!["device_id", "rssi", "snr", "phy", "frame_type", "payload"]);
+        let row = csv::StringRecord::from(vec!["dev-a", "-80", "5", "mioty", "uplink", "deadbeef"]);
+
+        assert!(parse_record(&row, &headers).is_err());
+    }
+
+    #[test]
+    fn reads_multiple_rows_from_a_file() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "device_id,ts,rssi,snr,phy,frame_type,payload").unwrap();
+        writeln!(file, "dev-a,1000,-80,5,mioty,uplink,ab").unwrap();
+        writeln!(file, "dev-b,2000,-70,6,mioty,uplink,cd").unwrap();
+
+        let source = CsvRecordSource::open(file.path()).unwrap();
+        let records = source.collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].device_id, "dev-a");
+        assert_eq!(records[1].device_id, "dev-b");
+    }
+}
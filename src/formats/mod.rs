@@ -0,0 +1,62 @@
+//! Input formats that can be turned into a stream of [`SdRecord`]s.
+//!
+//! Every format implements [`RecordSource`] and is consumed the same way, so
+//! [`crate::batching::BatchedRecordSource`] and [`crate::sink::write_batches`] don't
+//! need to know which format produced the records.
+
+mod csv;
+mod ndjson;
+mod xml;
+
+use std::path::Path;
+
+use iceberg::{Error, ErrorKind, Result};
+
+use crate::record::SdRecord;
+
+pub use csv::CsvRecordSource;
+pub use ndjson::NdjsonRecordSource;
+pub use xml::XmlRecordSource;
+
+/// A streaming source of [`SdRecord`]s, one per input format.
+pub trait RecordSource: Iterator<Item = Result<SdRecord>> {}
+
+impl<T: Iterator<Item = Result<SdRecord>>> RecordSource for T {}
+
+/// The input dialects `open` knows how to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Xml,
+    Ndjson,
+    Csv,
+}
+
+impl Format {
+    /// Infers the format from `path`'s extension (`.xml`, `.ndjson`/`.jsonl`, `.csv`).
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("xml") => Ok(Format::Xml),
+            Some("ndjson") | Some("jsonl") => Ok(Format::Ndjson),
+            Some("csv") => Ok(Format::Csv),
+            other => Err(Error::new(
+                ErrorKind::FeatureUnsupported,
+                format!("cannot infer input format from extension {other:?}; pass --format explicitly"),
+            )),
+        }
+    }
+}
+
+/// Opens `path` as a [`RecordSource`], using `format` if given or inferring it from
+/// `path`'s extension otherwise.
+pub fn open(path: &Path, format: Option<Format>) -> Result<Box<dyn RecordSource>> {
+    let format = match format {
+        Some(format) => format,
+        None => Format::from_path(path)?,
+    };
+
+    match format {
+        Format::Xml => Ok(Box::new(XmlRecordSource::open(path)?)),
+        Format::Ndjson => Ok(Box::new(NdjsonRecordSource::open(path)?)),
+        Format::Csv => Ok(Box::new(CsvRecordSource::open(path)?)),
+    }
+}
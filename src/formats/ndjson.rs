@@ -0,0 +1,146 @@
+//! Reads newline-delimited JSON exports of mioty uplinks, one object per line.
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use hex::FromHex;
+use serde_json::Value;
+
+use iceberg::{Error, ErrorKind, Result};
+
+use crate::record::SdRecord;
+
+/// Streams `SdRecord`s out of an NDJSON file, parsing one line at a time.
+pub struct NdjsonRecordSource {
+    lines: std::io::Lines<BufReader<std::fs::File>>,
+}
+
+impl NdjsonRecordSource {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+
+        Ok(Self {
+            lines: BufReader::new(file).lines(),
+        })
+    }
+}
+
+fn parse_line(line: &str) -> Result<SdRecord> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| Error::new(ErrorKind::DataInvalid, format!("NDJSON parse error: {e}")))?;
+
+    let field_str = |name: &str| -> Result<String> {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| Error::new(ErrorKind::DataInvalid, format!("missing field `{name}`")))
+    };
+    let field_i64 = |name: &str| -> Result<i64> {
+        value
+            .get(name)
+            .and_then(Value::as_i64)
+            .ok_or_else(|| Error::new(ErrorKind::DataInvalid, format!("missing field `{name}`")))
+    };
+
+    let mut record = SdRecord::new();
+    record.device_id = field_str("device_id")?;
+    record.ts = field_i64("ts")?;
+    record.rssi = field_i64("rssi")? as i32;
+    record.snr = field_i64("snr")? as i32;
+    record.phy = field_str("phy")?;
+    record.frame_type = field_str("frame_type")?;
+    record.payload = Vec::from_hex(field_str("payload")?)
+        .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+    record.mioty_qi_1 = value.get("mioty_qi_1").and_then(Value::as_f64);
+    record.mioty_qi_2 = value.get("mioty_qi_2").and_then(Value::as_i64).map(|v| v as i32);
+    record.mioty_qi_3 = value.get("mioty_qi_3").and_then(Value::as_i64).map(|v| v as i32);
+
+    Ok(record)
+}
+
+impl Iterator for NdjsonRecordSource {
+    type Item = Result<SdRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(Error::new(ErrorKind::DataInvalid, e.to_string()))),
+            };
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            return Some(parse_line(line));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_line_with_optional_fields() {
+        let line = r#"{"device_id":"dev-a","ts":1000,"rssi":-80,"snr":5,"phy":"mioty",
+            "frame_type":"uplink","payload":"deadbeef","mioty_qi_1":0.5,"mioty_qi_2":1,"mioty_qi_3":2}"#;
+
+        let record = parse_line(line).unwrap();
+        assert_eq!(record.device_id, "dev-a");
+        assert_eq!(record.ts, 1000);
+        assert_eq!(record.rssi, -80);
+        assert_eq!(record.snr, 5);
+        assert_eq!(record.payload, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(record.mioty_qi_1, Some(0.5));
+        assert_eq!(record.mioty_qi_2, Some(1));
+        assert_eq!(record.mioty_qi_3, Some(2));
+    }
+
+    #[test]
+    fn parses_line_missing_optional_fields() {
+        let line = r#"{"device_id":"dev-a","ts":1000,"rssi":-80,"snr":5,"phy":"mioty",
+            "frame_type":"uplink","payload":"deadbeef"}"#;
+
+        let record = parse_line(line).unwrap();
+        assert_eq!(record.mioty_qi_1, None);
+        assert_eq!(record.mioty_qi_2, None);
+        assert_eq!(record.mioty_qi_3, None);
+    }
+
+    #[test]
+    fn errors_on_missing_required_field() {
+        let line = r#"{"device_id":"dev-a","rssi":-80,"snr":5,"phy":"mioty",
+            "frame_type":"uplink","payload":"deadbeef"}"#;
+
+        assert!(parse_line(line).is_err());
+    }
+
+    #[test]
+    fn skips_blank_lines_between_records() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"{{"device_id":"dev-a","ts":1000,"rssi":-80,"snr":5,"phy":"mioty","frame_type":"uplink","payload":"ab"}}"#
+        )
+        .unwrap();
+        writeln!(file).unwrap();
+        writeln!(
+            file,
+            r#"{{"device_id":"dev-b","ts":2000,"rssi":-70,"snr":6,"phy":"mioty","frame_type":"uplink","payload":"cd"}}"#
+        )
+        .unwrap();
+
+        let source = NdjsonRecordSource::open(file.path()).unwrap();
+        let records = source.collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].device_id, "dev-a");
+        assert_eq!(records[1].device_id, "dev-b");
+    }
+}
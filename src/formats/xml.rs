@@ -0,0 +1,185 @@
+//! Reads the `<sdl>/<sd>` XML dialect, the original mioty gateway export format.
+
+use std::path::Path;
+
+use hex::FromHex;
+use quick_xml::events::Event;
+use quick_xml::{Reader, XmlVersion};
+
+use iceberg::{Error, ErrorKind, Result};
+
+use crate::record::SdRecord;
+
+/// Streams `SdRecord`s out of an `<sdl>/<sd>` XML file, reading the quick_xml event
+/// stream incrementally rather than materializing the whole document up front.
+pub struct XmlRecordSource {
+    reader: Reader<std::io::BufReader<std::fs::File>>,
+    buf: Vec<u8>,
+    current_device: String,
+    current_sd: SdRecord,
+    done: bool,
+}
+
+impl XmlRecordSource {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+
+        let mut reader = Reader::from_reader(std::io::BufReader::new(file));
+        reader.config_mut().trim_text(true);
+
+        Ok(Self {
+            reader,
+            buf: Vec::new(),
+            current_device: String::new(),
+            current_sd: SdRecord::new(),
+            done: false,
+        })
+    }
+
+    /// Reads XML events until the next complete `<sd>` record is found (returning
+    /// `Some`) or the file is exhausted (returning `None`).
+    fn next_record(&mut self) -> Result<Option<SdRecord>> {
+        loop {
+            match self
+                .reader
+                .read_event_into(&mut self.buf)
+                .map_err(|e| Error::new(ErrorKind::DataInvalid, format!("XML parse error: {e}")))?
+            {
+                Event::Start(ref e) => match e.name().as_ref() {
+                    b"sdl" => {
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == b"deviceId" {
+                                self.current_device = attr
+                                    .normalized_value(XmlVersion::Implicit1_0)
+                                    .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?
+                                    .into_owned();
+                            }
+                        }
+                    }
+                    b"sd" => {
+                        for attr in e.attributes().flatten() {
+                            let key = attr.key.as_ref();
+                            let val = attr
+                                .normalized_value(XmlVersion::Implicit1_0)
+                                .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?
+                                .into_owned();
+
+                            match key {
+                                b"ts" => {
+                                    self.current_sd.ts = val.parse::<i64>().map_err(|e| {
+                                        Error::new(ErrorKind::DataInvalid, e.to_string())
+                                    })?
+                                }
+                                b"rssi" => {
+                                    self.current_sd.rssi = val.parse::<i32>().map_err(|e| {
+                                        Error::new(ErrorKind::DataInvalid, e.to_string())
+                                    })?
+                                }
+                                b"snr" => {
+                                    self.current_sd.snr = val.parse::<i32>().map_err(|e| {
+                                        Error::new(ErrorKind::DataInvalid, e.to_string())
+                                    })?
+                                }
+                                b"phy" => self.current_sd.phy = val,
+                                b"type" => self.current_sd.frame_type = val,
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+
+                Event::Text(e) => {
+                    let text = std::str::from_utf8(e.as_ref())
+                        .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?
+                        .trim();
+
+                    if !text.is_empty() {
+                        self.current_sd.payload = Vec::from_hex(text)
+                            .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+                    }
+                }
+
+                Event::End(ref e) if e.name().as_ref() == b"sd" => {
+                    self.current_sd.device_id = self.current_device.clone();
+                    let record = std::mem::take(&mut self.current_sd);
+                    self.buf.clear();
+                    return Ok(Some(record));
+                }
+
+                Event::Eof => {
+                    self.done = true;
+                    return Ok(None);
+                }
+
+                _ => {}
+            }
+
+            self.buf.clear();
+        }
+    }
+}
+
+impl Iterator for XmlRecordSource {
+    type Item = Result<SdRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn fixture(xml: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn parses_records_under_a_device() {
+        let file = fixture(
+            r#"<sdl deviceId="dev-a">
+                <sd ts="1000" rssi="-80" snr="5" phy="mioty" type="uplink">deadbeef</sd>
+                <sd ts="2000" rssi="-70" snr="6" phy="mioty" type="uplink">cafe</sd>
+            </sdl>"#,
+        );
+
+        let records = XmlRecordSource::open(file.path())
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].device_id, "dev-a");
+        assert_eq!(records[0].ts, 1000);
+        assert_eq!(records[0].rssi, -80);
+        assert_eq!(records[0].payload, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(records[1].device_id, "dev-a");
+        assert_eq!(records[1].payload, vec![0xca, 0xfe]);
+    }
+
+    #[test]
+    fn errors_on_invalid_hex_payload() {
+        let file = fixture(
+            r#"<sdl deviceId="dev-a">
+                <sd ts="1000" rssi="-80" snr="5" phy="mioty" type="uplink">not-hex</sd>
+            </sdl>"#,
+        );
+
+        let records = XmlRecordSource::open(file.path())
+            .unwrap()
+            .collect::<Result<Vec<_>>>();
+
+        assert!(records.is_err());
+    }
+}
@@ -0,0 +1,172 @@
+//! Partitions `sd_test` by `identity(device_id)` and `day(ts)` so downstream engines
+//! can prune by device and by time range, the dominant access pattern for mioty
+//! telemetry.
+
+use std::collections::HashMap;
+
+use arrow_array::{Array, RecordBatch, StringArray, TimestampMicrosecondArray, UInt32Array};
+use arrow_select::take::take;
+use iceberg::spec::{Literal, PartitionSpec, Schema as IcebergSchema, Struct, Transform};
+use iceberg::{Error, ErrorKind, Result};
+
+/// `ts` is stored as microseconds since the epoch, matching Iceberg's `timestamp` type;
+/// this is how many of them make up one UTC day, the same unit `Transform::Day` uses.
+const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+/// Builds the `identity(device_id)` + `day(ts)` partition spec for `schema`. `ts` must
+/// be an Iceberg `timestamp`/`timestamptz` field: `day` is only valid on temporal
+/// source types.
+pub fn sd_partition_spec(schema: &IcebergSchema) -> Result<PartitionSpec> {
+    PartitionSpec::builder(schema.clone())
+        .add_partition_field("device_id", "device_id", Transform::Identity)
+        .and_then(|builder| builder.add_partition_field("ts", "ts_day", Transform::Day))
+        .and_then(|builder| builder.build())
+        .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))
+}
+
+/// One `(device_id, ts_day)` bucket, plus the Iceberg partition value it maps to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartitionKey {
+    pub device_id: String,
+    /// Days since the epoch, i.e. the value `Transform::Day` itself would compute.
+    pub ts_day: i32,
+}
+
+impl PartitionKey {
+    /// The Iceberg partition value (`Struct`) for this key, in partition-field order.
+    /// `ts_day` is a `date` literal: that's `day`'s declared result type, even though
+    /// both are represented as a day-count `i32` on the wire.
+    pub fn to_struct(&self) -> Struct {
+        Struct::from_iter([
+            Some(Literal::string(self.device_id.clone())),
+            Some(Literal::date(self.ts_day)),
+        ])
+    }
+}
+
+/// Splits `batch` into one sub-batch per `(device_id, day(ts))` bucket so each can be
+/// written to its own partitioned data file.
+pub fn split_by_partition(batch: &RecordBatch) -> Result<Vec<(PartitionKey, RecordBatch)>> {
+    let device_ids = batch
+        .column_by_name("device_id")
+        .and_then(|col| col.as_any().downcast_ref::<StringArray>())
+        .ok_or_else(|| Error::new(ErrorKind::DataInvalid, "batch has no string `device_id` column"))?;
+    let timestamps = batch
+        .column_by_name("ts")
+        .and_then(|col| col.as_any().downcast_ref::<TimestampMicrosecondArray>())
+        .ok_or_else(|| Error::new(ErrorKind::DataInvalid, "batch has no timestamp `ts` column"))?;
+
+    let mut groups: HashMap<PartitionKey, Vec<u32>> = HashMap::new();
+    for row in 0..batch.num_rows() {
+        let key = PartitionKey {
+            device_id: device_ids.value(row).to_string(),
+            ts_day: timestamps.value(row).div_euclid(MICROS_PER_DAY) as i32,
+        };
+        groups.entry(key).or_default().push(row as u32);
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, rows)| {
+            let indices = UInt32Array::from(rows);
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|col| take(col.as_ref(), &indices, None))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+
+            let sub_batch = RecordBatch::try_new(batch.schema(), columns)
+                .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))?;
+
+            Ok((key, sub_batch))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::Int32Array;
+    use arrow_schema::{DataType, Field, Schema as ArrowSchema, TimeUnit};
+    use iceberg::spec::{NestedField, PrimitiveType, Type};
+
+    use super::*;
+
+    fn test_batch() -> RecordBatch {
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("device_id", DataType::Utf8, false),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+            Field::new("rssi", DataType::Int32, false),
+        ]));
+
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["dev-a", "dev-a", "dev-b"])),
+                Arc::new(TimestampMicrosecondArray::from(vec![
+                    0,                      // dev-a, day 0
+                    MICROS_PER_DAY + 1,     // dev-a, day 1
+                    MICROS_PER_DAY + 2,     // dev-b, day 1
+                ])),
+                Arc::new(Int32Array::from(vec![-80, -81, -70])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn sd_partition_spec_partitions_by_device_and_day() {
+        let schema = IcebergSchema::builder()
+            .with_schema_id(0)
+            .with_fields(vec![
+                NestedField::required(1, "device_id", Type::Primitive(PrimitiveType::String)).into(),
+                NestedField::required(2, "ts", Type::Primitive(PrimitiveType::Timestamp)).into(),
+            ])
+            .build()
+            .unwrap();
+
+        let spec = sd_partition_spec(&schema).unwrap();
+        let names: Vec<_> = spec.fields().iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["device_id", "ts_day"]);
+        assert_eq!(spec.fields()[0].transform, Transform::Identity);
+        assert_eq!(spec.fields()[1].transform, Transform::Day);
+    }
+
+    #[test]
+    fn sd_partition_spec_rejects_non_temporal_ts() {
+        let schema = IcebergSchema::builder()
+            .with_schema_id(0)
+            .with_fields(vec![
+                NestedField::required(1, "device_id", Type::Primitive(PrimitiveType::String)).into(),
+                NestedField::required(2, "ts", Type::Primitive(PrimitiveType::Long)).into(),
+            ])
+            .build()
+            .unwrap();
+
+        assert!(sd_partition_spec(&schema).is_err());
+    }
+
+    #[test]
+    fn split_by_partition_groups_by_device_and_day() {
+        let batch = test_batch();
+        let groups = split_by_partition(&batch).unwrap();
+
+        assert_eq!(groups.len(), 3);
+        let total_rows: usize = groups.iter().map(|(_, b)| b.num_rows()).sum();
+        assert_eq!(total_rows, batch.num_rows());
+
+        let dev_a_day0 = groups
+            .iter()
+            .find(|(key, _)| key.device_id == "dev-a" && key.ts_day == 0)
+            .expect("dev-a/day0 bucket");
+        assert_eq!(dev_a_day0.1.num_rows(), 1);
+
+        let dev_a_day1 = groups
+            .iter()
+            .find(|(key, _)| key.device_id == "dev-a" && key.ts_day == 1)
+            .expect("dev-a/day1 bucket");
+        assert_eq!(dev_a_day1.1.num_rows(), 1);
+    }
+}
@@ -0,0 +1,89 @@
+//! The in-memory representation of one mioty uplink, shared by every input format.
+
+use std::sync::Arc;
+
+use arrow_array::{
+    BinaryArray, Float64Array, Int32Array, RecordBatch, StringArray, TimestampMicrosecondArray,
+};
+use arrow_schema::{DataType, Field, Schema as ArrowSchema, TimeUnit};
+use iceberg::{Error, ErrorKind, Result};
+
+#[derive(Debug, Clone)]
+pub struct SdRecord {
+    pub device_id: String,
+    /// Epoch microseconds, matching the Arrow/Iceberg `timestamp` type `ts` maps to.
+    pub ts: i64,
+    pub rssi: i32,
+    pub snr: i32,
+    pub phy: String,
+    pub frame_type: String,
+    pub payload: Vec<u8>,
+    pub mioty_qi_1: Option<f64>,
+    pub mioty_qi_2: Option<i32>,
+    pub mioty_qi_3: Option<i32>,
+}
+
+impl SdRecord {
+    pub fn new() -> Self {
+        Self {
+            device_id: String::new(),
+            ts: 0,
+            rssi: 0,
+            snr: 0,
+            phy: String::new(),
+            frame_type: String::new(),
+            payload: Vec::new(),
+            mioty_qi_1: None,
+            mioty_qi_2: None,
+            mioty_qi_3: None,
+        }
+    }
+}
+
+impl Default for SdRecord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn build_recordbatch(records: &[SdRecord]) -> Result<RecordBatch> {
+    let schema = Arc::new(ArrowSchema::new(vec![
+        Field::new("device_id", DataType::Utf8, false),
+        Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+        Field::new("rssi", DataType::Int32, false),
+        Field::new("snr", DataType::Int32, false),
+        Field::new("phy", DataType::Utf8, false),
+        Field::new("frame_type", DataType::Utf8, false),
+        Field::new("payload", DataType::Binary, false),
+        Field::new("mioty_qi_1", DataType::Float64, true),
+        Field::new("mioty_qi_2", DataType::Int32, true),
+        Field::new("mioty_qi_3", DataType::Int32, true),
+    ]));
+
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(StringArray::from(
+                records.iter().map(|r| r.device_id.as_str()).collect::<Vec<_>>(),
+            )),
+            Arc::new(TimestampMicrosecondArray::from(
+                records.iter().map(|r| r.ts).collect::<Vec<_>>(),
+            )),
+            Arc::new(Int32Array::from(records.iter().map(|r| r.rssi).collect::<Vec<_>>())),
+            Arc::new(Int32Array::from(records.iter().map(|r| r.snr).collect::<Vec<_>>())),
+            Arc::new(StringArray::from(
+                records.iter().map(|r| r.phy.as_str()).collect::<Vec<_>>(),
+            )),
+            Arc::new(StringArray::from(
+                records.iter().map(|r| r.frame_type.as_str()).collect::<Vec<_>>(),
+            )),
+            Arc::new(BinaryArray::from(
+                records.iter().map(|r| r.payload.as_slice()).collect::<Vec<_>>(),
+            )),
+            Arc::new(Float64Array::from(records.iter().map(|r| r.mioty_qi_1).collect::<Vec<_>>())),
+            Arc::new(Int32Array::from(records.iter().map(|r| r.mioty_qi_2).collect::<Vec<_>>())),
+            Arc::new(Int32Array::from(records.iter().map(|r| r.mioty_qi_3).collect::<Vec<_>>())),
+        ],
+    )
+    .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))
+}
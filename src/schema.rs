@@ -0,0 +1,150 @@
+//! Converts Arrow schemas into Iceberg schemas so the two sides can't drift apart.
+
+use std::sync::Arc;
+
+use arrow_schema::{DataType, Field, Schema as ArrowSchema, TimeUnit};
+use iceberg::spec::{ListType, NestedField, NestedFieldRef, PrimitiveType, Schema as IcebergSchema, StructType, Type};
+use iceberg::{Error, ErrorKind, Result};
+
+/// Builds an Iceberg [`IcebergSchema`] from an Arrow `schema`, assigning stable,
+/// monotonically increasing field ids starting at 1 in field order (depth-first
+/// for nested fields).
+pub fn arrow_to_iceberg_schema(schema: &ArrowSchema) -> Result<IcebergSchema> {
+    let mut next_id = 1;
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| arrow_field_to_nested_field(field, &mut next_id))
+        .collect::<Result<Vec<_>>>()?;
+
+    IcebergSchema::builder()
+        .with_schema_id(0)
+        .with_fields(fields)
+        .build()
+        .map_err(|e| Error::new(ErrorKind::DataInvalid, e.to_string()))
+}
+
+fn arrow_field_to_nested_field(field: &Field, next_id: &mut i32) -> Result<NestedFieldRef> {
+    let id = *next_id;
+    *next_id += 1;
+
+    let iceberg_type = arrow_type_to_iceberg_type(field.data_type(), next_id)?;
+    let nested_field = NestedField::new(id, field.name(), iceberg_type, !field.is_nullable());
+
+    Ok(Arc::new(nested_field))
+}
+
+fn arrow_type_to_iceberg_type(data_type: &DataType, next_id: &mut i32) -> Result<Type> {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 => Ok(Type::Primitive(PrimitiveType::String)),
+        DataType::Int32 => Ok(Type::Primitive(PrimitiveType::Int)),
+        DataType::Int64 => Ok(Type::Primitive(PrimitiveType::Long)),
+        DataType::Float64 => Ok(Type::Primitive(PrimitiveType::Double)),
+        DataType::Binary | DataType::LargeBinary => Ok(Type::Primitive(PrimitiveType::Binary)),
+        DataType::Timestamp(TimeUnit::Microsecond, None) => Ok(Type::Primitive(PrimitiveType::Timestamp)),
+        DataType::Struct(arrow_fields) => {
+            let fields = arrow_fields
+                .iter()
+                .map(|f| arrow_field_to_nested_field(f, next_id))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Type::Struct(StructType::new(fields)))
+        }
+        DataType::List(element) | DataType::LargeList(element) => {
+            let element_field = arrow_field_to_nested_field(element, next_id)?;
+            Ok(Type::List(ListType::new(element_field)))
+        }
+        other => Err(Error::new(
+            ErrorKind::FeatureUnsupported,
+            format!("no Iceberg mapping for Arrow type {other:?}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iceberg::spec::Type;
+
+    use super::*;
+
+    #[test]
+    fn assigns_ids_in_field_order() {
+        let schema = ArrowSchema::new(vec![
+            Field::new("device_id", DataType::Utf8, false),
+            Field::new("rssi", DataType::Int32, false),
+        ]);
+
+        let iceberg_schema = arrow_to_iceberg_schema(&schema).unwrap();
+
+        let fields = iceberg_schema.as_struct().fields();
+        assert_eq!(fields[0].id, 1);
+        assert_eq!(fields[0].name, "device_id");
+        assert_eq!(fields[1].id, 2);
+        assert_eq!(fields[1].name, "rssi");
+    }
+
+    #[test]
+    fn nullable_arrow_field_becomes_optional_iceberg_field() {
+        let schema = ArrowSchema::new(vec![
+            Field::new("required_field", DataType::Utf8, false),
+            Field::new("optional_field", DataType::Utf8, true),
+        ]);
+
+        let iceberg_schema = arrow_to_iceberg_schema(&schema).unwrap();
+
+        let fields = iceberg_schema.as_struct().fields();
+        assert!(fields[0].required);
+        assert!(!fields[1].required);
+    }
+
+    #[test]
+    fn maps_microsecond_timestamp_to_iceberg_timestamp() {
+        let schema = ArrowSchema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            false,
+        )]);
+
+        let iceberg_schema = arrow_to_iceberg_schema(&schema).unwrap();
+
+        assert_eq!(
+            iceberg_schema.as_struct().fields()[0].field_type.as_ref(),
+            &Type::Primitive(PrimitiveType::Timestamp)
+        );
+    }
+
+    #[test]
+    fn recurses_into_nested_struct_and_list_fields() {
+        let inner = DataType::Struct(
+            vec![Field::new("a", DataType::Int32, false)].into(),
+        );
+        let schema = ArrowSchema::new(vec![
+            Field::new("s", inner, false),
+            Field::new(
+                "l",
+                DataType::List(Arc::new(Field::new("item", DataType::Int32, true))),
+                false,
+            ),
+        ]);
+
+        let iceberg_schema = arrow_to_iceberg_schema(&schema).unwrap();
+
+        let fields = iceberg_schema.as_struct().fields();
+        // Ids are assigned depth-first: s=1, s.a=2, l=3, l.item=4.
+        let Type::Struct(s) = fields[0].field_type.as_ref() else {
+            panic!("expected struct type");
+        };
+        assert_eq!(s.fields()[0].id, 2);
+        let Type::List(l) = fields[1].field_type.as_ref() else {
+            panic!("expected list type");
+        };
+        assert_eq!(l.element_field.id, 4);
+        assert!(!l.element_field.required);
+    }
+
+    #[test]
+    fn errors_on_unmapped_arrow_type() {
+        let schema = ArrowSchema::new(vec![Field::new("d", DataType::Date32, false)]);
+
+        assert!(arrow_to_iceberg_schema(&schema).is_err());
+    }
+}
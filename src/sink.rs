@@ -0,0 +1,125 @@
+//! Commits parsed [`RecordBatch`]es to an Iceberg table.
+
+use arrow_array::RecordBatch;
+use iceberg::spec::PartitionKey as IcebergPartitionKey;
+use iceberg::transaction::{ApplyTransactionAction, Transaction};
+use iceberg::writer::base_writer::data_file_writer::DataFileWriterBuilder;
+use iceberg::writer::file_writer::location_generator::{
+    DefaultFileNameGenerator, DefaultLocationGenerator,
+};
+use iceberg::writer::file_writer::rolling_writer::RollingFileWriterBuilder;
+use iceberg::writer::file_writer::ParquetWriterBuilder;
+use iceberg::writer::{IcebergWriter, IcebergWriterBuilder};
+use iceberg::{Catalog, TableCreation, TableIdent};
+use iceberg::{Error, ErrorKind, Result};
+use parquet::file::properties::WriterProperties;
+
+use crate::partition::{sd_partition_spec, split_by_partition};
+use crate::schema::arrow_to_iceberg_schema;
+
+/// Loads the `sd_test` table, creating its namespace and the table itself on first use.
+/// The table's schema and `identity(device_id)`/`day(ts)` partition spec are derived
+/// from `batch`'s Arrow schema.
+async fn load_or_create_table(
+    catalog: &dyn Catalog,
+    table_ident: &TableIdent,
+    batch: &RecordBatch,
+) -> Result<iceberg::table::Table> {
+    let namespace = table_ident.namespace();
+    if !catalog.namespace_exists(namespace).await? {
+        catalog
+            .create_namespace(namespace, std::collections::HashMap::new())
+            .await?;
+    }
+
+    if catalog.table_exists(table_ident).await? {
+        return catalog.load_table(table_ident).await;
+    }
+
+    let schema = arrow_to_iceberg_schema(batch.schema().as_ref())?;
+    let partition_spec = sd_partition_spec(&schema)?;
+
+    let creation = TableCreation::builder()
+        .name(table_ident.name().to_string())
+        .schema(schema)
+        .partition_spec(partition_spec)
+        .build();
+
+    catalog.create_table(namespace, creation).await
+}
+
+/// Writes `batch` as one Parquet data file per `(device_id, day(ts))` partition bucket
+/// under `table`'s location, returning the written `DataFile` descriptors. Nothing is
+/// committed here; the caller adds them to a transaction.
+async fn write_data_file(
+    table: &iceberg::table::Table,
+    file_seq: u64,
+    batch: RecordBatch,
+) -> Result<Vec<iceberg::spec::DataFile>> {
+    let partition_spec = table.metadata().default_partition_spec().clone();
+    let schema = table.current_schema_ref();
+    let mut data_files = Vec::new();
+
+    for (part_seq, (key, partitioned_batch)) in split_by_partition(&batch)?.into_iter().enumerate() {
+        let location_generator = DefaultLocationGenerator::new(table.metadata())
+            .map_err(|e| Error::new(ErrorKind::Unexpected, e.to_string()))?;
+        let file_name_generator = DefaultFileNameGenerator::new(
+            "data".to_string(),
+            Some(format!("{file_seq}-{part_seq}")),
+            iceberg::spec::DataFileFormat::Parquet,
+        );
+
+        let parquet_writer_builder =
+            ParquetWriterBuilder::new(WriterProperties::builder().build(), schema.clone());
+        let rolling_writer_builder = RollingFileWriterBuilder::new_with_default_file_size(
+            parquet_writer_builder,
+            table.file_io().clone(),
+            location_generator,
+            file_name_generator,
+        );
+
+        let partition_key =
+            IcebergPartitionKey::new((*partition_spec).clone(), schema.clone(), key.to_struct());
+        let mut data_file_writer = DataFileWriterBuilder::new(rolling_writer_builder)
+            .build(Some(partition_key))
+            .await?;
+
+        data_file_writer.write(partitioned_batch).await?;
+        data_files.extend(data_file_writer.close().await?);
+    }
+
+    Ok(data_files)
+}
+
+/// Writes every batch from `batches` as partitioned Parquet data files and commits
+/// them all as a single append snapshot, creating the `sd_test` table first (from the
+/// first batch's Arrow schema) if it doesn't exist yet.
+///
+/// Batches are written one at a time, so peak memory stays bounded to a single batch
+/// no matter how many batches `batches` yields. Returns the id of the snapshot produced
+/// by the commit, or `None` if `batches` yielded nothing.
+pub async fn write_batches(
+    catalog: &dyn Catalog,
+    table_ident: &TableIdent,
+    mut batches: impl Iterator<Item = Result<RecordBatch>>,
+) -> Result<Option<i64>> {
+    let Some(first_batch) = batches.next().transpose()? else {
+        return Ok(None);
+    };
+
+    let table = load_or_create_table(catalog, table_ident, &first_batch).await?;
+
+    let mut data_files = Vec::new();
+    data_files.extend(write_data_file(&table, 0, first_batch).await?);
+
+    for (seq, batch) in batches.enumerate() {
+        data_files.extend(write_data_file(&table, seq as u64 + 1, batch?).await?);
+    }
+
+    let tx = Transaction::new(&table);
+    let append_action = tx.fast_append().add_data_files(data_files);
+    let tx = append_action.apply(tx)?;
+    let table = tx.commit(catalog).await?;
+
+    Ok(table.metadata().current_snapshot().map(|s| s.snapshot_id()))
+}